@@ -32,6 +32,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             message: Some(format!("Button clicked {} times", count)),
             data: Some(serde_json::json!({ "count": count })),
             request_id: None,
+            target: None,
         })
     }).await;
 
@@ -58,6 +59,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 "name": name 
             })),
             request_id: None,
+            target: None,
         })
     }).await;
 
@@ -71,6 +73,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     message: Some(format!("Name updated to: {}", name)),
                     data: None,
                     request_id: None,
+                    target: None,
                 });
             }
         }
@@ -80,6 +83,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             message: Some("Name input changed".to_string()),
             data: None,
             request_id: None,
+            target: None,
         })
     }).await;
 
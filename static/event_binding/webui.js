@@ -13,6 +13,13 @@ class WebUIClient {
         this.reconnectDelay = 1000;
         this.maxReconnectDelay = 30000;
         this.currentReconnectDelay = this.reconnectDelay;
+        this.reservedPrefix = '__';
+        this.heartbeatTimer = null;
+        this.latency = null;
+        this.authToken = null;
+        this.sessionToken = null;
+        this.polling = false;
+        this.chunks = new Map();
         
         this.init();
     }
@@ -25,6 +32,7 @@ class WebUIClient {
             if (!this.websocket || this.websocket.readyState !== WebSocket.OPEN) {
                 console.log('WebSocket connection failed, falling back to HTTP');
                 this.useWebSocket = false;
+                this.pollBroadcasts();
             }
         }, 2000);
     }
@@ -32,28 +40,46 @@ class WebUIClient {
     setupWebSocket() {
         try {
             const protocol = window.location.protocol === 'https:' ? 'wss:' : 'ws:';
-            const wsUrl = `${protocol}//${window.location.host}/ws`;
+            const session = this.sessionToken ? `?session=${encodeURIComponent(this.sessionToken)}` : '';
+            const wsUrl = `${protocol}//${window.location.host}/ws${session}`;
             
-            this.websocket = new WebSocket(wsUrl);
+            this.websocket = this.authToken
+                ? new WebSocket(wsUrl, ['webui', this.authToken])
+                : new WebSocket(wsUrl);
             
             this.websocket.onopen = () => {
                 console.log('WebSocket connected');
                 this.useWebSocket = true;
                 this.currentReconnectDelay = this.reconnectDelay;
+                this.chunks.clear();
                 this.processEventQueue();
             };
             
             this.websocket.onmessage = (event) => {
                 try {
-                    const response = JSON.parse(event.data);
-                    this.handleResponse(response);
+                    let message = JSON.parse(event.data);
+                    if (message.type === 'chunk') {
+                        const text = this.receiveChunk(message);
+                        if (text === null) {
+                            return;
+                        }
+                        message = JSON.parse(text);
+                    }
+                    this.handleMessage(message);
                 } catch (error) {
                     console.error('Error parsing WebSocket message:', error);
                 }
             };
             
-            this.websocket.onclose = () => {
-                console.log('WebSocket disconnected');
+            this.websocket.onclose = (event) => {
+                if (event.reason) {
+                    console.log(`WebSocket disconnected (${event.code}): ${event.reason}`);
+                } else {
+                    console.log('WebSocket disconnected');
+                }
+                document.dispatchEvent(new CustomEvent('webui:disconnect', {
+                    detail: { code: event.code, reason: event.reason }
+                }));
                 this.useWebSocket = false;
                 this.scheduleReconnect();
             };
@@ -68,6 +94,41 @@ class WebUIClient {
         }
     }
 
+    async pollBroadcasts() {
+        if (this.polling) {
+            return;
+        }
+        this.polling = true;
+        let since = null;
+
+        // Long-poll for broadcasts until the WebSocket comes back
+        while (!this.useWebSocket) {
+            try {
+                const url = since === null ? '/api/poll' : `/api/poll?since=${since}`;
+                const headers = this.authToken ? { 'Authorization': `Bearer ${this.authToken}` } : {};
+                const response = await fetch(url, { headers });
+                if (!response.ok) {
+                    throw new Error(`HTTP error! status: ${response.status}`);
+                }
+                const batch = await response.json();
+                since = batch.seq;
+                for (const message of batch.messages) {
+                    if (message.type === 'command') {
+                        this.handleCommand(message);
+                    } else if (message.type === 'dom') {
+                        this.applyDom(message);
+                    } else if (message.channel === 'presence') {
+                        this.handlePresence(message);
+                    }
+                }
+            } catch (error) {
+                console.error('Long-poll failed:', error);
+                await new Promise((resolve) => setTimeout(resolve, this.reconnectDelay));
+            }
+        }
+        this.polling = false;
+    }
+
     scheduleReconnect() {
         setTimeout(() => {
             console.log('Attempting to reconnect WebSocket...');
@@ -76,6 +137,56 @@ class WebUIClient {
         }, this.currentReconnectDelay);
     }
 
+    async heartbeat() {
+        const sentAt = Date.now();
+        const response = await this.sendEvent(`${this.reservedPrefix}heartbeat__`, 'ping', { sent_at: sentAt });
+        this.latency = Date.now() - sentAt;
+        return { latency: this.latency, serverTime: response.data.server_time_ms };
+    }
+
+    async syncTime() {
+        const t0 = Date.now();
+        const response = await this.sendEvent(`${this.reservedPrefix}time__`, 'sync', { t0 });
+        const t3 = Date.now();
+        const { t1, t2 } = response.data;
+        return {
+            offset: ((t1 - t0) + (t2 - t3)) / 2,
+            rtt: (t3 - t0) - (t2 - t1),
+            monotonic: response.data.monotonic_ms
+        };
+    }
+
+    cancel(requestId) {
+        return this.sendEvent(`${this.reservedPrefix}cancel__`, 'cancel', { request_id: requestId });
+    }
+
+    sendMessage(kind, data = {}) {
+        if (!this.websocket || this.websocket.readyState !== WebSocket.OPEN) {
+            return Promise.reject(new Error('WebSocket is not connected'));
+        }
+        return this.sendEventViaWebSocket({ kind, data });
+    }
+
+    startHeartbeat(interval = 15000) {
+        this.stopHeartbeat();
+        this.heartbeatTimer = setInterval(() => {
+            if (!this.websocket || this.websocket.readyState !== WebSocket.OPEN) {
+                return;
+            }
+            this.heartbeat().catch(() => {
+                console.log('Heartbeat failed, reconnecting WebSocket');
+                this.websocket.close();
+            });
+        }, interval);
+    }
+
+    stopHeartbeat() {
+        if (this.heartbeatTimer) {
+            clearInterval(this.heartbeatTimer);
+            this.heartbeatTimer = null;
+        }
+    }
+
     async sendEvent(elementId, eventType, data = {}) {
         const event = {
             element_id: elementId,
@@ -96,29 +207,38 @@ class WebUIClient {
                 const requestId = ++this.requestId;
                 event.request_id = requestId;
                 
-                this.pendingRequests.set(requestId, { resolve, reject });
+                const pending = { resolve, reject, elementId: event.element_id, timer: null };
+                this.pendingRequests.set(requestId, pending);
                 this.websocket.send(JSON.stringify(event));
-                
-                // Timeout after 10 seconds
-                setTimeout(() => {
-                    if (this.pendingRequests.has(requestId)) {
-                        this.pendingRequests.delete(requestId);
-                        reject(new Error('Request timeout'));
-                    }
-                }, 10000);
+                this.armRequestTimeout(requestId, pending);
             } catch (error) {
                 reject(error);
             }
         });
     }
 
+    armRequestTimeout(requestId, pending) {
+        // Timeout after 10 seconds without a response or interim update
+        clearTimeout(pending.timer);
+        pending.timer = setTimeout(() => {
+            if (this.pendingRequests.get(requestId) === pending) {
+                this.pendingRequests.delete(requestId);
+                pending.reject(new Error('Request timeout'));
+            }
+        }, 10000);
+    }
+
     async sendEventViaHTTP(event) {
         try {
+            const headers = {
+                'Content-Type': 'application/json',
+            };
+            if (this.authToken) {
+                headers['Authorization'] = `Bearer ${this.authToken}`;
+            }
             const response = await fetch('/api/event', {
                 method: 'POST',
-                headers: {
-                    'Content-Type': 'application/json',
-                },
+                headers,
                 body: JSON.stringify(event)
             });
             
@@ -126,7 +246,7 @@ class WebUIClient {
                 throw new Error(`HTTP error! status: ${response.status}`);
             }
             
-            return await response.json();
+            return this.normalizeResponse(await response.json());
         } catch (error) {
             console.error('HTTP request failed:', error);
             throw error;
@@ -135,10 +255,39 @@ class WebUIClient {
 
     handleResponse(response) {
         if (response.request_id && this.pendingRequests.has(response.request_id)) {
-            const { resolve } = this.pendingRequests.get(response.request_id);
+            const { resolve, timer } = this.pendingRequests.get(response.request_id);
             this.pendingRequests.delete(response.request_id);
+            clearTimeout(timer);
             resolve(response);
+        } else if (response.request_id) {
+            // Further responses streamed for a request that was already answered
+            if (response.target) {
+                this.applyToTarget(response);
+            }
+            document.dispatchEvent(new CustomEvent('webui:stream', { detail: response }));
+        }
+    }
+
+    handleInterim(response) {
+        const pending = this.pendingRequests.get(response.request_id);
+        if (!pending) {
+            return;
+        }
+
+        this.armRequestTimeout(response.request_id, pending);
+        if (response.target) {
+            this.applyToTarget(response);
         }
+        const element = document.getElementById(pending.elementId) || document;
+        element.dispatchEvent(new CustomEvent('webui:interim', { detail: response, bubbles: true }));
+    }
+
+    handleCommand(command) {
+        document.dispatchEvent(new CustomEvent(command.event, { detail: command.data }));
+    }
+
+    handlePresence(presence) {
+        document.dispatchEvent(new CustomEvent('webui:presence', { detail: presence.data }));
     }
 
     processEventQueue() {
@@ -179,6 +328,22 @@ class WebUIClient {
                 const eventData = this.extractEventData(domEvent, element);
                 
                 const response = await this.sendEvent(elementId, eventType, eventData);
+
+                if (response.target) {
+                    this.applyToTarget(response);
+                }
+
+                if (response.data && Array.isArray(response.data.dom)) {
+                    this.applyDomBatch(response.data.dom);
+                }
+
+                if (response.data && response.data.download) {
+                    this.download(response.data.download);
+                }
+
+                if (element.tagName === 'FORM' || (response.data && response.data.errors)) {
+                    this.showFormErrors(element, response.success ? {} : (response.data || {}).errors);
+                }
                 
                 if (callback) {
                     callback(response, domEvent);
@@ -196,6 +361,153 @@ class WebUIClient {
         });
     }
 
+    download({ filename, url }) {
+        const link = document.createElement('a');
+        link.href = url;
+        link.download = filename;
+        document.body.appendChild(link);
+        link.click();
+        link.remove();
+    }
+
+    applyToTarget(response) {
+        const target = document.getElementById(response.target);
+        if (!target) {
+            console.error(`Target element with ID '${response.target}' not found`);
+            return;
+        }
+
+        const data = response.data || {};
+        if (data.html !== undefined) {
+            target.innerHTML = data.html;
+        } else if (data.text !== undefined) {
+            target.textContent = data.text;
+        } else if (data.value !== undefined) {
+            target.value = data.value;
+        } else if (response.message) {
+            target.textContent = response.message;
+        }
+
+        target.dispatchEvent(new CustomEvent('webui:response', { detail: response }));
+    }
+
+    showFormErrors(element, errors) {
+        const scope = element.tagName === 'FORM' ? element : document;
+        scope.querySelectorAll('.webui-error').forEach((note) => note.remove());
+        scope.querySelectorAll('[aria-invalid="true"]').forEach((input) => input.removeAttribute('aria-invalid'));
+
+        for (const [field, message] of Object.entries(errors || {})) {
+            let input = element.tagName === 'FORM' ? element.elements.namedItem(field) : null;
+            if (input instanceof RadioNodeList) {
+                input = input[0];
+            }
+            input = input || document.getElementById(field);
+            if (!input) {
+                console.error(`Field '${field}' not found for error: ${message}`);
+                continue;
+            }
+
+            input.setAttribute('aria-invalid', 'true');
+            const note = document.createElement('span');
+            note.className = 'webui-error';
+            note.textContent = message;
+            input.insertAdjacentElement('afterend', note);
+        }
+    }
+
+    handleMessage(message) {
+        if (message.type === 'session') {
+            this.sessionToken = message.token;
+        } else if (message.type === 'command') {
+            this.handleCommand(message);
+        } else if (message.type === 'dom') {
+            this.applyDom(message);
+        } else if (message.type === 'interim') {
+            this.handleInterim(message);
+        } else if (message.channel === 'presence') {
+            this.handlePresence(message);
+        } else {
+            this.handleResponse(this.normalizeResponse(message));
+        }
+    }
+
+    normalizeResponse(response) {
+        if (response.error && response.message === undefined) {
+            response.message = response.error.message;
+            response.data = response.error.data;
+        }
+        return response;
+    }
+
+    receiveChunk(chunk) {
+        // Collect fragments of a large message until all have arrived
+        let parts = this.chunks.get(chunk.id);
+        if (!parts) {
+            parts = new Array(chunk.count);
+            parts.received = 0;
+            this.chunks.set(chunk.id, parts);
+        }
+        if (parts[chunk.index] === undefined) {
+            parts[chunk.index] = chunk.data;
+            parts.received++;
+        }
+        if (parts.received < chunk.count) {
+            return null;
+        }
+        this.chunks.delete(chunk.id);
+        return parts.join('');
+    }
+
+    applyDom(update) {
+        const element = document.getElementById(update.id);
+        if (!element) {
+            console.error(`Element with ID '${update.id}' not found`);
+            return;
+        }
+        this.applyDomAction(element, update);
+    }
+
+    applyDomBatch(updates) {
+        const elements = [];
+        for (const update of updates) {
+            const element = update.action === 'toast' ? null : document.getElementById(update.id);
+            if (update.action !== 'toast' && !element) {
+                console.error(`Element with ID '${update.id}' not found, skipping DOM update batch`);
+                return;
+            }
+            elements.push(element);
+        }
+        updates.forEach((update, index) => this.applyDomAction(elements[index], update));
+    }
+
+    applyDomAction(element, update) {
+        switch (update.action) {
+            case 'text':
+                element.textContent = update.value;
+                break;
+            case 'html':
+                element.innerHTML = update.value;
+                break;
+            case 'value':
+                element.value = update.value;
+                break;
+            case 'add_class':
+                element.classList.add(update.value);
+                break;
+            case 'remove_class':
+                element.classList.remove(update.value);
+                break;
+            case 'attribute':
+                element.setAttribute(update.name, update.value);
+                break;
+            case 'toast':
+                document.dispatchEvent(new CustomEvent('toast', { detail: { text: update.value } }));
+                break;
+            default:
+                console.error(`Unknown DOM action '${update.action}'`);
+        }
+    }
+
     extractEventData(domEvent, element) {
         const data = {};
         
@@ -36,6 +36,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 "count": count,
             })),
             request_id: event.request_id,
+            target: None,
         }) 
     }).await;
 
@@ -53,6 +54,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             message: Some(format!("Input received: {}", input_value)),
             data: Some(serde_json::json!({ "processed_input": input_value.to_uppercase() })),
             request_id: event.request_id,
+            target: None,
         })
     }).await;
 
@@ -85,6 +87,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 "user_email": email,
             })),
             request_id: event.request_id,
+            target: None,
         })
     }).await;
 
@@ -101,6 +104,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 message: Some("Required field is missing".to_string()),
                 data: None,
                 request_id: event.request_id,
+                target: None,
             });
         }
 
@@ -111,6 +115,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             message: Some("Custom action completed".to_string()),
             data: Some(serde_json::json!({ "result": "success" })),
             request_id: event.request_id,
+            target: None,
         })
     }).await;
 
@@ -148,6 +153,7 @@ fn create_success_response(message: &str, data: serde_json::Value, request_id: O
         message: Some(message.to_string()),
         data: Some(data),
         request_id,
+        target: None,
     }
 }
 
@@ -157,5 +163,6 @@ fn create_error_response(error: &str, request_id: Option<u32>) -> UIResponse {
         message: Some(error.to_string()),
         data: None,
         request_id,
+        target: None,
     }
 }
\ No newline at end of file